@@ -1,3 +1,4 @@
+use regex::Regex;
 use std::collections::HashMap;
 use std::env;
 use std::fs;
@@ -6,57 +7,206 @@ use std::process::{Command, exit};
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// An inclusive-or-exclusive numeric bound pair, e.g. `1..=65535` or `0.0..1.0`.
+#[derive(Debug, Clone, PartialEq)]
+struct Range<T> {
+    min: T,
+    max: T,
+    max_inclusive: bool,
+}
+
+impl<T: PartialOrd + std::fmt::Display> Range<T> {
+    fn contains(&self, value: &T) -> bool {
+        *value >= self.min && (if self.max_inclusive { *value <= self.max } else { *value < self.max })
+    }
+
+    fn describe(&self) -> String {
+        if self.max_inclusive {
+            format!("{}..={}", self.min, self.max)
+        } else {
+            format!("{}..{}", self.min, self.max)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 enum VarType {
-    Str,
-    Int,
-    Float,
+    Str { pattern: Option<String> },
+    Int { range: Option<Range<i64>> },
+    Float { range: Option<Range<f64>> },
     Bool,
     Any,
+    Enum(Vec<String>),
 }
 
 impl VarType {
-    fn from_str(s: &str) -> Option<Self> {
+    /// Parses a type token such as `int`, `int(1..=65535)`, `enum(a,b,c)` or
+    /// `str(/^sk-[A-Za-z0-9]{20,}$/)` into a `VarType`. The part before an
+    /// optional `(...)` selects the base type; the part inside selects the
+    /// constraint.
+    fn parse(s: &str) -> Result<Self, String> {
         let clean = s.trim().trim_matches(|c| c == '"' || c == '\'');
-        match clean {
-            "str" => Some(VarType::Str),
-            "int" => Some(VarType::Int),
-            "float" => Some(VarType::Float),
-            "bool" => Some(VarType::Bool),
-            "any" => Some(VarType::Any),
-            _ => None,
+
+        let (base, arg) = match clean.find('(') {
+            Some(open) => {
+                if !clean.ends_with(')') {
+                    return Err(format!("unterminated constraint in type '{}'", clean));
+                }
+                (clean[..open].trim(), Some(clean[open + 1..clean.len() - 1].trim()))
+            }
+            None => (clean, None),
+        };
+
+        match base {
+            "str" => Ok(VarType::Str {
+                pattern: arg.map(parse_regex_literal).transpose()?,
+            }),
+            "int" => Ok(VarType::Int {
+                range: arg.map(parse_range).transpose()?,
+            }),
+            "float" => Ok(VarType::Float {
+                range: arg.map(parse_range).transpose()?,
+            }),
+            "bool" => Ok(VarType::Bool),
+            "any" => Ok(VarType::Any),
+            "enum" => {
+                let members: Vec<String> = arg
+                    .ok_or_else(|| "enum type requires a value set, e.g. enum(a,b,c)".to_string())?
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect();
+                if members.is_empty() {
+                    return Err("enum type requires at least one value".to_string());
+                }
+                Ok(VarType::Enum(members))
+            }
+            _ => Err(format!("unknown type '{}'", base)),
         }
     }
 
-    fn validate(&self, value: &str) -> bool {
+    fn validate(&self, value: &str) -> Result<(), String> {
         match self {
-            VarType::Str => !value.is_empty(),
-            VarType::Int => value.parse::<i64>().is_ok(),
-            VarType::Float => value.parse::<f64>().is_ok(),
-            VarType::Bool => value.parse::<bool>().is_ok(),
-            VarType::Any => true,
+            VarType::Str { pattern } => {
+                if value.is_empty() {
+                    return Err("expected a non-empty string".to_string());
+                }
+                if let Some(pattern) = pattern {
+                    let anchored = format!("^(?:{})$", pattern);
+                    let re = Regex::new(&anchored)
+                        .map_err(|e| format!("invalid regex '{}': {}", pattern, e))?;
+                    if !re.is_match(value) {
+                        return Err(format!(
+                            "expected value matching /{}/, got '{}'",
+                            pattern, value
+                        ));
+                    }
+                }
+                Ok(())
+            }
+            VarType::Int { range } => {
+                let parsed = value
+                    .parse::<i64>()
+                    .map_err(|_| format!("expected int, got '{}'", value))?;
+                match range {
+                    Some(range) if !range.contains(&parsed) => Err(format!(
+                        "expected int in {}, got {}",
+                        range.describe(),
+                        parsed
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            VarType::Float { range } => {
+                let parsed = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("expected float, got '{}'", value))?;
+                match range {
+                    Some(range) if !range.contains(&parsed) => Err(format!(
+                        "expected float in {}, got {}",
+                        range.describe(),
+                        parsed
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            VarType::Bool => value
+                .parse::<bool>()
+                .map(|_| ())
+                .map_err(|_| format!("expected bool, got '{}'", value)),
+            VarType::Any => Ok(()),
+            VarType::Enum(members) => {
+                if members.iter().any(|m| m == value) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "expected one of [{}], got '{}'",
+                        members.join(", "),
+                        value
+                    ))
+                }
+            }
         }
     }
 
     fn as_str(&self) -> &'static str {
         match self {
-            VarType::Str => "str",
-            VarType::Int => "int",
-            VarType::Float => "float",
+            VarType::Str { .. } => "str",
+            VarType::Int { .. } => "int",
+            VarType::Float { .. } => "float",
             VarType::Bool => "bool",
             VarType::Any => "any",
+            VarType::Enum(_) => "enum",
         }
     }
 }
 
+fn parse_regex_literal(s: &str) -> Result<String, String> {
+    let s = s.trim();
+    if s.len() >= 2 && s.starts_with('/') && s.ends_with('/') {
+        Ok(s[1..s.len() - 1].to_string())
+    } else {
+        Err(format!("invalid regex literal '{}', expected '/pattern/'", s))
+    }
+}
+
+fn parse_range<T: std::str::FromStr>(s: &str) -> Result<Range<T>, String> {
+    let (sep, max_inclusive, sep_len) = if let Some(pos) = s.find("..=") {
+        (pos, true, 3)
+    } else if let Some(pos) = s.find("..") {
+        (pos, false, 2)
+    } else {
+        return Err(format!(
+            "invalid range '{}', expected 'min..max' or 'min..=max'",
+            s
+        ));
+    };
+
+    let min_str = s[..sep].trim();
+    let max_str = s[sep + sep_len..].trim();
+    let min = min_str
+        .parse::<T>()
+        .map_err(|_| format!("invalid range bound '{}'", min_str))?;
+    let max = max_str
+        .parse::<T>()
+        .map_err(|_| format!("invalid range bound '{}'", max_str))?;
+    Ok(Range {
+        min,
+        max,
+        max_inclusive,
+    })
+}
+
 struct Config {
     required: HashMap<String, VarType>,
     optional: HashMap<String, VarType>,
+    defaults: HashMap<String, String>,
 }
 
 fn parse_config(content: &str) -> Result<Config, String> {
     let mut required = HashMap::new();
     let mut optional = HashMap::new();
+    let mut raw_defaults: HashMap<String, (String, usize)> = HashMap::new();
     let mut current_section = "";
 
     for (i, line) in content.lines().enumerate() {
@@ -84,25 +234,31 @@ fn parse_config(content: &str) -> Result<Config, String> {
             let val_str = line[pos + 1..]
                 .trim()
                 .trim_matches(|c| c == '"' || c == '\'');
-            let var_type = VarType::from_str(val_str).ok_or_else(|| {
-                format!(
-                    "Line {}: Invalid type '{}' for key '{}'",
-                    i + 1,
-                    val_str,
-                    key
-                )
-            })?;
 
             match current_section {
-                "required" => {
-                    required.insert(key, var_type);
+                "required" | "optional" => {
+                    let var_type = VarType::parse(val_str).map_err(|e| {
+                        format!(
+                            "Line {}: Invalid type '{}' for key '{}': {}",
+                            i + 1,
+                            val_str,
+                            key,
+                            e
+                        )
+                    })?;
+
+                    if current_section == "required" {
+                        required.insert(key, var_type);
+                    } else {
+                        optional.insert(key, var_type);
+                    }
                 }
-                "optional" => {
-                    optional.insert(key, var_type);
+                "defaults" => {
+                    raw_defaults.insert(key, (val_str.to_string(), i + 1));
                 }
                 _ => {
                     return Err(format!(
-                        "Line {}: Assignment outside of [required] or [optional] section",
+                        "Line {}: Assignment outside of [required], [optional] or [defaults] section",
                         i + 1
                     ));
                 }
@@ -112,7 +268,288 @@ fn parse_config(content: &str) -> Result<Config, String> {
         }
     }
 
-    Ok(Config { required, optional })
+    let mut defaults = HashMap::new();
+    for (key, (value, line_no)) in raw_defaults {
+        if required.contains_key(&key) {
+            return Err(format!(
+                "Line {}: Default for '{}' is pointless; '{}' is required and has no gap to fill",
+                line_no, key, key
+            ));
+        }
+        let var_type = optional.get(&key).ok_or_else(|| {
+            format!(
+                "Line {}: Default for undeclared variable '{}'",
+                line_no, key
+            )
+        })?;
+        var_type.validate(&value).map_err(|e| {
+            format!(
+                "Line {}: Default value for '{}' is invalid: {}",
+                line_no, key, e
+            )
+        })?;
+        defaults.insert(key, value);
+    }
+
+    Ok(Config {
+        required,
+        optional,
+        defaults,
+    })
+}
+
+/// Parses `.env`-style `KEY=value` lines, honoring the same single/double
+/// quoting rules as `split_args` for the value portion.
+fn parse_env_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let pos = line
+            .find('=')
+            .ok_or_else(|| format!("Line {}: Invalid line format", i + 1))?;
+        let key = line[..pos].trim().to_string();
+        if key.is_empty() {
+            return Err(format!("Line {}: Missing variable name", i + 1));
+        }
+
+        let raw_value = line[pos + 1..].trim();
+        let value = if raw_value.starts_with('"') || raw_value.starts_with('\'') {
+            split_args(raw_value).first().cloned().unwrap_or_default()
+        } else {
+            match raw_value.find('#') {
+                Some(comment_pos) => raw_value[..comment_pos].trim().to_string(),
+                None => raw_value.to_string(),
+            }
+        };
+
+        pairs.push((key, value));
+    }
+
+    Ok(pairs)
+}
+
+fn resolve_var(name: &str, strict: bool) -> Result<String, String> {
+    match env::var(name) {
+        Ok(val) => Ok(val),
+        Err(_) if strict => Err(format!("Variable '{}' is not set", name)),
+        Err(_) => Ok(String::new()),
+    }
+}
+
+fn run_command_substitution(cmd_str: &str) -> Result<String, String> {
+    #[cfg(unix)]
+    let output = Command::new("sh").arg("-c").arg(cmd_str).output();
+    #[cfg(not(unix))]
+    let output = Command::new("cmd").args(["/C", cmd_str]).output();
+
+    let output = output.map_err(|e| {
+        format!(
+            "Failed to run command substitution '$({})': {}",
+            cmd_str, e
+        )
+    })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Command substitution '$({})' exited with non-zero status",
+            cmd_str
+        ));
+    }
+
+    String::from_utf8(output.stdout)
+        .map(|s| s.trim_end_matches('\n').to_string())
+        .map_err(|e| {
+            format!(
+                "Command substitution '$({})' produced invalid UTF-8: {}",
+                cmd_str, e
+            )
+        })
+}
+
+/// Resolves the `NAME`, `{NAME}` or `(command)` reference immediately
+/// following a `$` that `chars` is positioned just after. Shared by
+/// `expand_args` and `split_and_expand_args` so both expand a `$...`
+/// reference identically.
+fn expand_dollar(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    strict: bool,
+) -> Result<String, String> {
+    match chars.peek() {
+        Some('(') => {
+            chars.next();
+            let mut cmd_str = String::new();
+            let mut depth = 1;
+            for nc in chars.by_ref() {
+                match nc {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                cmd_str.push(nc);
+            }
+            if depth != 0 {
+                return Err(format!("Unterminated command substitution '$({}'", cmd_str));
+            }
+            run_command_substitution(&cmd_str)
+        }
+        Some('{') => {
+            chars.next();
+            let mut name = String::new();
+            let mut closed = false;
+            for nc in chars.by_ref() {
+                if nc == '}' {
+                    closed = true;
+                    break;
+                }
+                name.push(nc);
+            }
+            if !closed {
+                return Err(format!("Unterminated variable reference '${{{}'", name));
+            }
+            resolve_var(&name, strict)
+        }
+        Some(&nc) if nc.is_ascii_alphabetic() || nc == '_' => {
+            let mut name = String::new();
+            while let Some(&nc) = chars.peek() {
+                if nc.is_ascii_alphanumeric() || nc == '_' {
+                    name.push(nc);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            resolve_var(&name, strict)
+        }
+        _ => Ok("$".to_string()),
+    }
+}
+
+/// Expand `$NAME`, `${NAME}` and `$(command)` references in `s`. Expansion
+/// honors the same single/double quote rules as `split_args`: text inside
+/// single quotes is left untouched, text inside double quotes (and outside
+/// any quotes) is expanded. A backslash before `$` escapes it to a literal
+/// dollar sign. Unknown variables expand to an empty string unless `strict`
+/// is set, in which case they error.
+///
+/// This is for tokens that are already split (the multi-argument CLI form,
+/// where the OS already did the splitting); it does not re-tokenize. For
+/// the single-string command form, use `split_and_expand_args` instead so
+/// an expanded value containing a quote character can't be reinterpreted
+/// as token syntax.
+fn expand_args(s: &str, strict: bool) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = s.chars().peekable();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+
+    while let Some(c) = chars.next() {
+        if in_single_quote {
+            result.push(c);
+            if c == '\'' {
+                in_single_quote = false;
+            }
+            continue;
+        }
+
+        if c == '\\' && chars.peek() == Some(&'$') {
+            chars.next();
+            result.push('$');
+            continue;
+        }
+
+        if c == '\'' && !in_double_quote {
+            in_single_quote = true;
+            result.push(c);
+            continue;
+        }
+
+        if c == '"' {
+            in_double_quote = !in_double_quote;
+            result.push(c);
+            continue;
+        }
+
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        result.push_str(&expand_dollar(&mut chars, strict)?);
+    }
+
+    Ok(result)
+}
+
+/// Splits a single command string into argv entries exactly like
+/// `split_args`, but expands `$NAME`/`${NAME}`/`$(command)` references into
+/// each token as it is built, rather than expanding the whole string first.
+/// Doing expansion and splitting in one pass means a substituted value that
+/// happens to contain a `'` or `"` cannot be reinterpreted as quote syntax
+/// and corrupt the boundaries of later tokens. As with `split_args`, text
+/// inside single quotes is left untouched and not expanded.
+fn split_and_expand_args(s: &str, strict: bool) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current_arg = String::new();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_single_quote {
+            if c == '\'' {
+                in_single_quote = false;
+            } else {
+                current_arg.push(c);
+            }
+            continue;
+        }
+
+        if in_double_quote {
+            match c {
+                '"' => in_double_quote = false,
+                '\\' if chars.peek() == Some(&'$') => {
+                    chars.next();
+                    current_arg.push('$');
+                }
+                '$' => current_arg.push_str(&expand_dollar(&mut chars, strict)?),
+                c => current_arg.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '\'' => in_single_quote = true,
+            '"' => in_double_quote = true,
+            c if c.is_whitespace() => {
+                if !current_arg.is_empty() {
+                    args.push(current_arg.clone());
+                    current_arg.clear();
+                }
+            }
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                current_arg.push('$');
+            }
+            '$' => current_arg.push_str(&expand_dollar(&mut chars, strict)?),
+            c => current_arg.push(c),
+        }
+    }
+
+    if !current_arg.is_empty() {
+        args.push(current_arg);
+    }
+
+    Ok(args)
 }
 
 fn split_args(s: &str) -> Vec<String> {
@@ -155,36 +592,238 @@ fn split_args(s: &str) -> Vec<String> {
     args
 }
 
+/// Infers a `VarType` for a value pulled from the environment by trying
+/// each parse in turn, falling back to `str` when nothing else fits.
+/// Empty values infer as `any` since `str` rejects the empty string and
+/// would make the generated config reject the very value it saw.
+fn infer_var_type(value: &str) -> VarType {
+    if value.is_empty() {
+        VarType::Any
+    } else if value.parse::<i64>().is_ok() {
+        VarType::Int { range: None }
+    } else if value.parse::<f64>().is_ok() {
+        VarType::Float { range: None }
+    } else if value.parse::<bool>().is_ok() {
+        VarType::Bool
+    } else {
+        VarType::Str { pattern: None }
+    }
+}
+
+/// Scaffolds a starter `AssertEnv.toml` from the current process
+/// environment, placing every discovered variable under `[optional]` for
+/// the user to promote as needed.
+fn run_init(path: &str, force: bool) -> Result<(), String> {
+    if !force && fs::metadata(path).is_ok() {
+        return Err(format!(
+            "'{}' already exists; use --force to overwrite",
+            path
+        ));
+    }
+
+    let mut vars: Vec<(String, String)> = env::vars().collect();
+    vars.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut content = String::new();
+    content.push_str("# Generated by `assert-env --init` from the current environment.\n");
+    content.push_str("# Everything discovered below is listed as optional; promote the\n");
+    content.push_str("# variables your program actually needs up to [required].\n\n");
+    content.push_str("[required]\n\n[optional]\n");
+
+    for (key, value) in &vars {
+        content.push_str(&format!("{} = \"{}\"\n", key, infer_var_type(value).as_str()));
+    }
+
+    fs::write(path, content).map_err(|e| format!("Could not write '{}': {}", path, e))
+}
+
+/// The validation status of a single declared variable, used to build the
+/// `--check`/`--format json` report independently of the human-readable
+/// error lines.
+struct VarReport {
+    name: String,
+    section: &'static str,
+    declared_type: String,
+    present: bool,
+    valid: bool,
+    value: Option<String>,
+    error: Option<String>,
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", json_escape(s))
+}
+
+fn json_opt_string(s: &Option<String>) -> String {
+    match s {
+        Some(v) => json_string(v),
+        None => "null".to_string(),
+    }
+}
+
+impl VarReport {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"name\":{},\"section\":{},\"type\":{},\"present\":{},\"valid\":{},\"value\":{},\"error\":{}}}",
+            json_string(&self.name),
+            json_string(self.section),
+            json_string(&self.declared_type),
+            self.present,
+            self.valid,
+            json_opt_string(&self.value),
+            json_opt_string(&self.error),
+        )
+    }
+}
+
+/// Renders the full `--format json` report: overall pass/fail, one entry
+/// per declared variable, and the same human-readable error lines used in
+/// text mode.
+fn render_report_json(ok: bool, reports: &[VarReport], errors: &[String]) -> String {
+    let variables = reports
+        .iter()
+        .map(VarReport::to_json)
+        .collect::<Vec<_>>()
+        .join(",");
+    let errs = errors
+        .iter()
+        .map(|e| json_string(e))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        "{{\"ok\":{},\"variables\":[{}],\"errors\":[{}]}}",
+        ok, variables, errs
+    )
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args.iter().any(|arg| arg == "-h" || arg == "--help") {
         println!("assert-env - Simple runtime assertions for environment variables\n");
         println!("Usage:");
-        println!("  assert-env [-f <path/to/toml>] <command>\n");
+        println!("  assert-env [-f <path/to/toml>] <command>");
+        println!("  assert-env --init [-f <path/to/toml>] [--force]\n");
         println!("Options:");
         println!("  -f, --file <path>  Path to AssertEnv.toml (default: AssertEnv.toml)");
+        println!("  --env-file <path>  Load KEY=value pairs into the environment before validating");
+        println!("  --strict-env       Error on unresolved $VAR references instead of blanking them");
+        println!("  --check            Validate only; never execute the command");
+        println!("  --format <fmt>     Output format for validation results: text (default) or json");
+        println!("  --init             Scaffold AssertEnv.toml from the current environment");
+        println!("  --force            With --init, overwrite an existing config file");
         println!("  -h, --help         Show this help message\n");
         println!("Example:");
         println!("  assert-env \"node index.js\"");
         exit(0);
     }
 
+    if args.get(1).map(String::as_str) == Some("--init") {
+        let mut toml_path = "AssertEnv.toml".to_string();
+        let mut force = false;
+        let mut i = 2;
+
+        while i < args.len() {
+            match args[i].as_str() {
+                "-f" | "--file" => {
+                    if i + 1 >= args.len() {
+                        eprintln!("Error: Missing path after {} flag", args[i]);
+                        exit(1);
+                    }
+                    toml_path = args[i + 1].clone();
+                    i += 2;
+                }
+                "--force" => {
+                    force = true;
+                    i += 1;
+                }
+                other => {
+                    eprintln!("Error: Unrecognized argument '{}' for --init", other);
+                    exit(1);
+                }
+            }
+        }
+
+        match run_init(&toml_path, force) {
+            Ok(()) => {
+                println!("Wrote '{}'", toml_path);
+                exit(0);
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                exit(1);
+            }
+        }
+    }
+
     if args.len() < 2 {
         eprintln!("Error: No command provided. Use -h for help.");
         exit(1);
     }
 
     let mut toml_path = "AssertEnv.toml".to_string();
+    let mut strict_env = false;
+    let mut env_file_path: Option<String> = None;
+    let mut check = false;
+    let mut format = "text".to_string();
     let mut cmd_start_idx = 1;
 
-    if args[1] == "-f" || args[1] == "--file" {
-        if args.len() < 3 {
-            eprintln!("Error: Missing path after {} flag", args[1]);
-            exit(1);
+    while cmd_start_idx < args.len() {
+        match args[cmd_start_idx].as_str() {
+            "-f" | "--file" => {
+                if cmd_start_idx + 1 >= args.len() {
+                    eprintln!("Error: Missing path after {} flag", args[cmd_start_idx]);
+                    exit(1);
+                }
+                toml_path = args[cmd_start_idx + 1].clone();
+                cmd_start_idx += 2;
+            }
+            "--strict-env" => {
+                strict_env = true;
+                cmd_start_idx += 1;
+            }
+            "--env-file" => {
+                if cmd_start_idx + 1 >= args.len() {
+                    eprintln!("Error: Missing path after {} flag", args[cmd_start_idx]);
+                    exit(1);
+                }
+                env_file_path = Some(args[cmd_start_idx + 1].clone());
+                cmd_start_idx += 2;
+            }
+            "--check" => {
+                check = true;
+                cmd_start_idx += 1;
+            }
+            "--format" => {
+                if cmd_start_idx + 1 >= args.len() {
+                    eprintln!("Error: Missing format after {} flag", args[cmd_start_idx]);
+                    exit(1);
+                }
+                format = args[cmd_start_idx + 1].clone();
+                if format != "text" && format != "json" {
+                    eprintln!("Error: Unknown format '{}', expected 'text' or 'json'", format);
+                    exit(1);
+                }
+                cmd_start_idx += 2;
+            }
+            _ => break,
         }
-        toml_path = args[2].clone();
-        cmd_start_idx = 3;
     }
 
     if cmd_start_idx >= args.len() {
@@ -192,6 +831,32 @@ fn main() {
         exit(1);
     }
 
+    if let Some(env_file_path) = &env_file_path {
+        let content = match fs::read_to_string(env_file_path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error: Could not read env file '{}': {}", env_file_path, e);
+                exit(1);
+            }
+        };
+
+        let pairs = match parse_env_file(&content) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Error: Parsing env file '{}' failed: {}", env_file_path, e);
+                exit(1);
+            }
+        };
+
+        for (key, value) in pairs {
+            if env::var(&key).is_err() {
+                unsafe {
+                    env::set_var(key, value);
+                }
+            }
+        }
+    }
+
     let content = match fs::read_to_string(&toml_path) {
         Ok(c) => c,
         Err(e) => {
@@ -209,50 +874,118 @@ fn main() {
     };
 
     let mut errors = Vec::new();
+    let mut reports = Vec::new();
 
     for (key, var_type) in &config.required {
+        let declared_type = var_type.as_str().to_string();
         match env::var(key) {
             Ok(val) => {
-                if val.is_empty() {
-                    errors.push(format!("Required variable '{}' is empty", key));
-                } else if !var_type.validate(&val) {
-                    errors.push(format!(
-                        "Required variable '{}' has invalid value '{}' (expected {})",
-                        key,
-                        val,
-                        var_type.as_str()
-                    ));
+                let result = var_type.validate(&val);
+                if let Err(e) = &result {
+                    errors.push(format!("Required variable '{}': {}", key, e));
                 }
+                reports.push(VarReport {
+                    name: key.clone(),
+                    section: "required",
+                    declared_type,
+                    present: true,
+                    valid: result.is_ok(),
+                    value: Some(val),
+                    error: result.err(),
+                });
             }
             Err(_) => {
                 errors.push(format!("Required variable '{}' is missing", key));
+                reports.push(VarReport {
+                    name: key.clone(),
+                    section: "required",
+                    declared_type,
+                    present: false,
+                    valid: false,
+                    value: None,
+                    error: Some("missing".to_string()),
+                });
             }
         }
     }
 
     for (key, var_type) in &config.optional {
-        if let Ok(val) = env::var(key)
-            && !var_type.validate(&val)
-        {
-            errors.push(format!(
-                "Optional variable '{}' has invalid value '{}' (expected {})",
-                key,
-                val,
-                var_type.as_str()
-            ));
+        let declared_type = var_type.as_str().to_string();
+        match env::var(key) {
+            Ok(val) => {
+                let result = var_type.validate(&val);
+                if let Err(e) = &result {
+                    errors.push(format!("Optional variable '{}': {}", key, e));
+                }
+                reports.push(VarReport {
+                    name: key.clone(),
+                    section: "optional",
+                    declared_type,
+                    present: true,
+                    valid: result.is_ok(),
+                    value: Some(val),
+                    error: result.err(),
+                });
+            }
+            Err(_) => {
+                reports.push(VarReport {
+                    name: key.clone(),
+                    section: "optional",
+                    declared_type,
+                    present: false,
+                    valid: true,
+                    value: None,
+                    error: None,
+                });
+            }
         }
     }
 
-    if !errors.is_empty() {
-        for err in errors {
-            eprintln!("Assertion Error: {}", err);
+    let ok = errors.is_empty();
+
+    // `config.required`/`config.optional` are HashMaps, so iteration order
+    // above is arbitrary; sort for a stable, diffable report.
+    reports.sort_by(|a, b| a.name.cmp(&b.name));
+    errors.sort();
+
+    if format == "json" {
+        println!("{}", render_report_json(ok, &reports, &errors));
+        if !ok {
+            exit(1);
+        }
+        if check {
+            exit(0);
+        }
+    } else {
+        if !ok {
+            for err in &errors {
+                eprintln!("Assertion Error: {}", err);
+            }
+            exit(1);
+        }
+        if check {
+            println!("OK");
+            exit(0);
+        }
+    }
+
+    for (key, value) in &config.defaults {
+        if config.optional.contains_key(key) && env::var(key).is_err() {
+            unsafe {
+                env::set_var(key, value);
+            }
         }
-        exit(1);
     }
 
     // Execute the command
     let (cmd_bin, cmd_args) = if args.len() - cmd_start_idx == 1 {
-        let parts = split_args(&args[cmd_start_idx]);
+        let parts = match split_and_expand_args(&args[cmd_start_idx], strict_env) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                exit(1);
+            }
+        };
         if parts.is_empty() {
             eprintln!("Error: Empty command provided");
             exit(1);
@@ -261,9 +994,18 @@ fn main() {
         let args = parts[1..].to_vec();
         (bin, args)
     } else {
-        let bin = args[cmd_start_idx].clone();
-        let cmd_args = args[cmd_start_idx + 1..].to_vec();
-        (bin, cmd_args)
+        let mut expanded = Vec::with_capacity(args.len() - cmd_start_idx);
+        for raw in &args[cmd_start_idx..] {
+            match expand_args(raw, strict_env) {
+                Ok(e) => expanded.push(e),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    exit(1);
+                }
+            }
+        }
+        let bin = expanded.remove(0);
+        (bin, expanded)
     };
 
     let mut cmd = Command::new(cmd_bin);
@@ -294,14 +1036,62 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_from_str() {
-        assert_eq!(VarType::from_str("str"), Some(VarType::Str));
-        assert_eq!(VarType::from_str("\"str\""), Some(VarType::Str));
-        assert_eq!(VarType::from_str("'int'"), Some(VarType::Int));
-        assert_eq!(VarType::from_str("  float  "), Some(VarType::Float));
-        assert_eq!(VarType::from_str("\"any\""), Some(VarType::Any));
-        assert_eq!(VarType::from_str("invalid"), None);
-        assert_eq!(VarType::from_str(""), None);
+    fn test_parse_base_types() {
+        assert_eq!(VarType::parse("str"), Ok(VarType::Str { pattern: None }));
+        assert_eq!(
+            VarType::parse("\"str\""),
+            Ok(VarType::Str { pattern: None })
+        );
+        assert_eq!(VarType::parse("'int'"), Ok(VarType::Int { range: None }));
+        assert_eq!(
+            VarType::parse("  float  "),
+            Ok(VarType::Float { range: None })
+        );
+        assert_eq!(VarType::parse("\"any\""), Ok(VarType::Any));
+        assert!(VarType::parse("invalid").is_err());
+        assert!(VarType::parse("").is_err());
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        assert_eq!(
+            VarType::parse("int(1..=65535)"),
+            Ok(VarType::Int {
+                range: Some(Range {
+                    min: 1,
+                    max: 65535,
+                    max_inclusive: true
+                })
+            })
+        );
+        assert_eq!(
+            VarType::parse("float(0.0..1.0)"),
+            Ok(VarType::Float {
+                range: Some(Range {
+                    min: 0.0,
+                    max: 1.0,
+                    max_inclusive: false
+                })
+            })
+        );
+        assert_eq!(
+            VarType::parse("enum(debug,info, warn,error)"),
+            Ok(VarType::Enum(vec![
+                "debug".to_string(),
+                "info".to_string(),
+                "warn".to_string(),
+                "error".to_string()
+            ]))
+        );
+        assert_eq!(
+            VarType::parse("str(/^sk-[A-Za-z0-9]{20,}$/)"),
+            Ok(VarType::Str {
+                pattern: Some("^sk-[A-Za-z0-9]{20,}$".to_string())
+            })
+        );
+        assert!(VarType::parse("int(abc..=5)").is_err());
+        assert!(VarType::parse("enum()").is_err());
+        assert!(VarType::parse("str(no-slashes)").is_err());
     }
 
     #[test]
@@ -315,13 +1105,29 @@ KEY2 = 'int'
   KEY3=float
 KEY4=any
 KEY5=bool
+KEY6=\"int(1..=100)\"
+KEY7=\"enum(a,b,c)\"
 ";
         let config = parse_config(content).unwrap();
-        assert_eq!(config.required.get("KEY1"), Some(&VarType::Str));
-        assert_eq!(config.required.get("KEY2"), Some(&VarType::Int));
-        assert_eq!(config.optional.get("KEY3"), Some(&VarType::Float));
+        assert_eq!(config.required.get("KEY1"), Some(&VarType::Str { pattern: None }));
+        assert_eq!(config.required.get("KEY2"), Some(&VarType::Int { range: None }));
+        assert_eq!(config.optional.get("KEY3"), Some(&VarType::Float { range: None }));
         assert_eq!(config.optional.get("KEY4"), Some(&VarType::Any));
         assert_eq!(config.optional.get("KEY5"), Some(&VarType::Bool));
+        assert_eq!(
+            config.optional.get("KEY6"),
+            Some(&VarType::Int {
+                range: Some(Range {
+                    min: 1,
+                    max: 100,
+                    max_inclusive: true
+                })
+            })
+        );
+        assert_eq!(
+            config.optional.get("KEY7"),
+            Some(&VarType::Enum(vec!["a".to_string(), "b".to_string(), "c".to_string()]))
+        );
     }
 
     #[test]
@@ -342,35 +1148,190 @@ KEY5=bool
             parse_config("[unknown]\nKEY=str").is_err(),
             "Assignment in unknown section should fail"
         );
+        assert!(
+            parse_config("[optional]\nPORT=int\n[defaults]\nPORT=abc").is_err(),
+            "Default value must match the declared type"
+        );
+        assert!(
+            parse_config("[defaults]\nUNKNOWN=str").is_err(),
+            "Default for an undeclared variable should fail"
+        );
+        assert!(
+            parse_config("[required]\nNAME=str\n[defaults]\nNAME=world").is_err(),
+            "Default for a required variable should fail, not silently no-op"
+        );
+    }
+
+    #[test]
+    fn test_parse_config_defaults() {
+        let content = "
+[optional]
+PORT = \"int\"
+LOG_LEVEL = \"enum(debug,info)\"
+
+[defaults]
+PORT = \"8080\"
+LOG_LEVEL = \"info\"
+";
+        let config = parse_config(content).unwrap();
+        assert_eq!(config.defaults.get("PORT"), Some(&"8080".to_string()));
+        assert_eq!(config.defaults.get("LOG_LEVEL"), Some(&"info".to_string()));
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = "
+# comment
+FOO=bar
+BAZ=\"hello world\" # trailing comment
+QUX='single quoted'
+
+EMPTY=
+";
+        let pairs = parse_env_file(content).unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("FOO".to_string(), "bar".to_string()),
+                ("BAZ".to_string(), "hello world".to_string()),
+                ("QUX".to_string(), "single quoted".to_string()),
+                ("EMPTY".to_string(), "".to_string()),
+            ]
+        );
+        assert!(parse_env_file("NOEQUALS").is_err());
+    }
+
+    #[test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("plain"), "plain");
+        assert_eq!(json_escape("quote\"back\\slash"), "quote\\\"back\\\\slash");
+        assert_eq!(json_escape("line\nbreak"), "line\\nbreak");
+    }
+
+    #[test]
+    fn test_render_report_json() {
+        let reports = vec![
+            VarReport {
+                name: "PORT".to_string(),
+                section: "required",
+                declared_type: "int".to_string(),
+                present: true,
+                valid: true,
+                value: Some("8080".to_string()),
+                error: None,
+            },
+            VarReport {
+                name: "NAME".to_string(),
+                section: "required",
+                declared_type: "str".to_string(),
+                present: false,
+                valid: false,
+                value: None,
+                error: Some("missing".to_string()),
+            },
+        ];
+        let errors = vec!["Required variable 'NAME' is missing".to_string()];
+        let json = render_report_json(false, &reports, &errors);
+        assert!(json.contains("\"ok\":false"));
+        assert!(json.contains("\"name\":\"PORT\""));
+        assert!(json.contains("\"value\":\"8080\""));
+        assert!(json.contains("\"name\":\"NAME\""));
+        assert!(json.contains("\"value\":null"));
+        assert!(json.contains("\"errors\":[\"Required variable 'NAME' is missing\"]"));
     }
 
     #[test]
     fn test_validate() {
+        let str_type = VarType::Str { pattern: None };
+        let int_type = VarType::Int { range: None };
+        let float_type = VarType::Float { range: None };
+
         // String
-        assert!(VarType::Str.validate("hello"));
-        assert!(!VarType::Str.validate(""));
+        assert!(str_type.validate("hello").is_ok());
+        assert!(str_type.validate("").is_err());
 
         // Integer
-        assert!(VarType::Int.validate("123"));
-        assert!(VarType::Int.validate("-123"));
-        assert!(VarType::Int.validate("0"));
-        assert!(!VarType::Int.validate("12.3")); // Should fail since it's a float
-        assert!(!VarType::Int.validate("abc"));
+        assert!(int_type.validate("123").is_ok());
+        assert!(int_type.validate("-123").is_ok());
+        assert!(int_type.validate("0").is_ok());
+        assert!(int_type.validate("12.3").is_err()); // Should fail since it's a float
+        assert!(int_type.validate("abc").is_err());
 
         // Float
-        assert!(VarType::Float.validate("1.23"));
-        assert!(VarType::Float.validate("-1.23"));
-        assert!(VarType::Float.validate("0.0"));
-        assert!(VarType::Float.validate("123")); // Valid float
-        assert!(!VarType::Float.validate("abc"));
-        assert!(VarType::Bool.validate("true"));
-        assert!(VarType::Bool.validate("false"));
-        assert!(!VarType::Bool.validate("1"));
-        assert!(!VarType::Bool.validate("yes"));
+        assert!(float_type.validate("1.23").is_ok());
+        assert!(float_type.validate("-1.23").is_ok());
+        assert!(float_type.validate("0.0").is_ok());
+        assert!(float_type.validate("123").is_ok()); // Valid float
+        assert!(float_type.validate("abc").is_err());
+        assert!(VarType::Bool.validate("true").is_ok());
+        assert!(VarType::Bool.validate("false").is_ok());
+        assert!(VarType::Bool.validate("1").is_err());
+        assert!(VarType::Bool.validate("yes").is_err());
 
         // Any
-        assert!(VarType::Any.validate(""));
-        assert!(VarType::Any.validate("anything"));
+        assert!(VarType::Any.validate("").is_ok());
+        assert!(VarType::Any.validate("anything").is_ok());
+    }
+
+    #[test]
+    fn test_validate_constraints() {
+        let port = VarType::Int {
+            range: Some(Range {
+                min: 1,
+                max: 65535,
+                max_inclusive: true,
+            }),
+        };
+        assert!(port.validate("8080").is_ok());
+        assert!(port.validate("0").is_err());
+        assert_eq!(
+            port.validate("70000").unwrap_err(),
+            "expected int in 1..=65535, got 70000"
+        );
+
+        let ratio = VarType::Float {
+            range: Some(Range {
+                min: 0.0,
+                max: 1.0,
+                max_inclusive: false,
+            }),
+        };
+        assert!(ratio.validate("0.5").is_ok());
+        assert!(ratio.validate("1.0").is_err());
+
+        let level = VarType::Enum(vec![
+            "debug".to_string(),
+            "info".to_string(),
+            "warn".to_string(),
+            "error".to_string(),
+        ]);
+        assert!(level.validate("info").is_ok());
+        assert!(level.validate("trace").is_err());
+
+        let api_key = VarType::Str {
+            pattern: Some("^sk-[A-Za-z0-9]{20,}$".to_string()),
+        };
+        assert!(api_key.validate("sk-abcdefghijklmnopqrstuvwxyz").is_ok());
+        assert!(api_key.validate("not-a-key").is_err());
+
+        // Patterns match the whole value, not just a substring, with or
+        // without user-supplied anchors.
+        let level_word = VarType::Str {
+            pattern: Some("info".to_string()),
+        };
+        assert!(level_word.validate("info").is_ok());
+        assert!(level_word.validate("superinfoXYZ").is_err());
+    }
+
+    #[test]
+    fn test_infer_var_type() {
+        assert_eq!(infer_var_type("123"), VarType::Int { range: None });
+        assert_eq!(infer_var_type("-42"), VarType::Int { range: None });
+        assert_eq!(infer_var_type("1.5"), VarType::Float { range: None });
+        assert_eq!(infer_var_type("true"), VarType::Bool);
+        assert_eq!(infer_var_type("false"), VarType::Bool);
+        assert_eq!(infer_var_type("hello"), VarType::Str { pattern: None });
+        assert_eq!(infer_var_type(""), VarType::Any);
     }
 
     #[test]
@@ -389,8 +1350,8 @@ DB_PASS = '"any"'
             config.err()
         );
         let config = config.unwrap();
-        assert_eq!(config.required.get("DB_HOST"), Some(&VarType::Str));
-        assert_eq!(config.required.get("DB_PORT"), Some(&VarType::Int));
+        assert_eq!(config.required.get("DB_HOST"), Some(&VarType::Str { pattern: None }));
+        assert_eq!(config.required.get("DB_PORT"), Some(&VarType::Int { range: None }));
         assert_eq!(config.required.get("DB_USER"), Some(&VarType::Any));
         assert_eq!(config.required.get("DB_PASS"), Some(&VarType::Any));
     }
@@ -425,4 +1386,62 @@ DB_PASS = '"any"'
         );
         assert_eq!(split_args("   echo   hello   "), vec!["echo", "hello"]);
     }
+
+    #[test]
+    fn test_expand_args() {
+        unsafe {
+            env::set_var("ASSERT_ENV_TEST_PORT", "8080");
+        }
+        assert_eq!(
+            expand_args("node server.js --port $ASSERT_ENV_TEST_PORT", false).unwrap(),
+            "node server.js --port 8080"
+        );
+        assert_eq!(
+            expand_args("node server.js --port ${ASSERT_ENV_TEST_PORT}", false).unwrap(),
+            "node server.js --port 8080"
+        );
+        assert_eq!(
+            expand_args("echo 'literal $ASSERT_ENV_TEST_PORT'", false).unwrap(),
+            "echo 'literal $ASSERT_ENV_TEST_PORT'"
+        );
+        assert_eq!(
+            expand_args("echo \"value $ASSERT_ENV_TEST_PORT\"", false).unwrap(),
+            "echo \"value 8080\""
+        );
+        assert_eq!(
+            expand_args("echo \\$ASSERT_ENV_TEST_PORT", false).unwrap(),
+            "echo $ASSERT_ENV_TEST_PORT"
+        );
+        assert_eq!(
+            expand_args("echo $ASSERT_ENV_TEST_MISSING", false).unwrap(),
+            "echo "
+        );
+        assert!(expand_args("echo $ASSERT_ENV_TEST_MISSING", true).is_err());
+        unsafe {
+            env::remove_var("ASSERT_ENV_TEST_PORT");
+        }
+    }
+
+    #[test]
+    fn test_split_and_expand_args() {
+        unsafe {
+            env::set_var("ASSERT_ENV_TEST_GREETING", "it's a test");
+        }
+        assert_eq!(
+            split_and_expand_args("./showargs.sh $ASSERT_ENV_TEST_GREETING 'literal arg'", false)
+                .unwrap(),
+            vec!["./showargs.sh", "it's a test", "literal arg"]
+        );
+        assert_eq!(
+            split_and_expand_args("echo \"value $ASSERT_ENV_TEST_GREETING\"", false).unwrap(),
+            vec!["echo", "value it's a test"]
+        );
+        assert_eq!(
+            split_and_expand_args("echo 'literal $ASSERT_ENV_TEST_GREETING'", false).unwrap(),
+            vec!["echo", "literal $ASSERT_ENV_TEST_GREETING"]
+        );
+        unsafe {
+            env::remove_var("ASSERT_ENV_TEST_GREETING");
+        }
+    }
 }